@@ -1,12 +1,28 @@
 fn main() {
-    // Temporarily disabled protobuf compilation until we resolve dependencies
-    println!("cargo:warning=Proto compilation disabled for test build");
-    
-    // Uncomment when ready to use protobuf again
-    /*
+    println!("cargo:rerun-if-changed=proto/");
+    println!("cargo:rerun-if-env-changed=PROTOC");
+
+    // `src/proto/mod.rs`'s generated bindings are checked in precisely
+    // because the wasm toolchains this client ships from don't reliably
+    // have `protoc` on `PATH`. Only regenerate (and clobber them) when a
+    // `protoc` is actually available; otherwise fall back to what's
+    // committed so a protoc-less environment still builds.
+    if !protoc_available() {
+        println!(
+            "cargo:warning=protoc not found; skipping proto codegen and using the committed src/proto/*.rs bindings"
+        );
+        return;
+    }
+
+    // `tonic::transport` pulls in hyper/tokio's reactor, neither of which
+    // exists in wasm32-unknown-unknown. We still want the generated request
+    // types and client stubs, so we disable the transport impl and drive the
+    // generated client over `tonic_web_wasm_client` instead (see
+    // `src/client.rs`).
     tonic_build::configure()
         .build_server(false)
         .build_client(true)
+        .build_transport(false)
         .out_dir("src/proto")
         .compile(
             &[
@@ -15,8 +31,19 @@ fn main() {
                 "proto/ingest/asset/asset.proto",
             ],
             &["proto"],
-        ).expect("Failed to compile protos");
-    
-    println!("cargo:rerun-if-changed=proto/");
-    */
-} 
\ No newline at end of file
+        )
+        .expect("Failed to compile protos");
+}
+
+/// Mirrors how `prost-build` itself locates `protoc`: respect `$PROTOC` if
+/// set, otherwise look for `protoc` on `PATH`.
+fn protoc_available() -> bool {
+    let protoc = std::env::var_os("PROTOC")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::path::PathBuf::from("protoc"));
+
+    std::process::Command::new(protoc)
+        .arg("--version")
+        .output()
+        .is_ok()
+}
@@ -0,0 +1,30 @@
+//! Bearer-token auth for `EventClient`, modeled on the per-event gRPC
+//! authorization the rest of the pipeline uses: a token is attached as an
+//! `authorization: Bearer <token>` metadata header on every outgoing
+//! request via a [`tonic::service::Interceptor`].
+
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+#[derive(Clone)]
+pub(crate) struct AuthInterceptor {
+    token: Option<String>,
+}
+
+impl AuthInterceptor {
+    pub(crate) fn new(token: Option<String>) -> Self {
+        Self { token }
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        if let Some(token) = &self.token {
+            let value = format!("Bearer {token}")
+                .parse()
+                .map_err(|_| Status::invalid_argument("auth token is not valid metadata"))?;
+            request.metadata_mut().insert("authorization", value);
+        }
+        Ok(request)
+    }
+}
@@ -0,0 +1,145 @@
+//! gRPC-web plumbing for [`EventClient`](crate::EventClient).
+//!
+//! Browsers can't open the raw HTTP/2 sockets `tonic::transport` expects, so
+//! the generated `EventServiceClient` is driven over
+//! [`tonic_web_wasm_client`], which speaks gRPC-web through `fetch`/XHR
+//! instead.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use js_sys::{Array, Object, Reflect};
+use tonic::service::interceptor::InterceptedService;
+use tonic_web_wasm_client::Client as WebClient;
+use wasm_bindgen::prelude::*;
+
+use crate::auth::AuthInterceptor;
+use crate::error::ClientError;
+use crate::proto::event_service::event_service_client::EventServiceClient;
+use crate::proto::event_service::GetEventsRequest;
+use crate::proto::ingest::processors::token_transfer::TokenTransferEvent;
+
+/// Builds the generated client against `server_address`, speaking gRPC-web
+/// and attaching `auth_token` (if any) as a bearer token on every request.
+pub(crate) fn connect(
+    server_address: &str,
+    auth_token: Option<String>,
+) -> EventServiceClient<InterceptedService<WebClient, AuthInterceptor>> {
+    let channel = WebClient::new(server_address.to_string());
+    EventServiceClient::with_interceptor(channel, AuthInterceptor::new(auth_token))
+}
+
+/// Opens the `GetTTPEvents` server-streaming RPC and returns the raw decoded
+/// stream, for callers that want to read it incrementally
+/// ([`crate::stream`]) rather than collect it up front ([`fetch_events`]).
+pub(crate) async fn open_stream(
+    server_address: &str,
+    auth_token: Option<String>,
+    start_ledger: u32,
+    end_ledger: u32,
+    account_ids: Vec<String>,
+) -> Result<tonic::codec::Streaming<TokenTransferEvent>, ClientError> {
+    let mut client = connect(server_address, auth_token);
+    let request = GetEventsRequest {
+        start_ledger,
+        end_ledger,
+        account_ids,
+    };
+
+    client
+        .get_ttp_events(request)
+        .await
+        .map(|response| response.into_inner())
+        .map_err(ClientError::from_status)
+}
+
+/// Issues `GetTTPEvents` and collects the whole stream into `{events,
+/// errors}`, two separate JS arrays so a caller never has to inspect an
+/// element to tell a decoded event from a reported error. Used by
+/// [`EventClient::get_ttp_events`]; callers that want events as they arrive
+/// should use [`EventClient::subscribe`] instead.
+///
+/// A `Decode` status is a single malformed message, not a dead stream: it's
+/// collected into `errors` and reading continues. Any other status
+/// (`Unauthorized`/`Transport`) ends the fetch and is rejected, matching
+/// `open_stream`'s own failure mode. A panic converting a decoded event is
+/// caught the same way and collected into `errors` as
+/// [`ClientError::Internal`].
+pub(crate) async fn fetch_events(
+    server_address: &str,
+    auth_token: Option<String>,
+    start_ledger: u32,
+    end_ledger: u32,
+    account_ids: Vec<String>,
+) -> Result<Object, JsValue> {
+    let mut stream =
+        open_stream(server_address, auth_token, start_ledger, end_ledger, account_ids).await?;
+
+    let events = Array::new();
+    let errors = Array::new();
+    loop {
+        match stream.message().await {
+            Ok(Some(event)) => match panic::catch_unwind(AssertUnwindSafe(|| event_to_js(&event))) {
+                Ok(js_event) => events.push(&js_event),
+                Err(payload) => errors.push(&ClientError::from_panic(payload).into()),
+            },
+            Ok(None) => break,
+            Err(status) => match ClientError::from_status(status) {
+                ClientError::Decode(message) => {
+                    errors.push(&ClientError::Decode(message).into());
+                    continue;
+                }
+                err => return Err(err.into()),
+            },
+        };
+    }
+
+    let result = Object::new();
+    let _ = Reflect::set(&result, &"events".into(), &events);
+    let _ = Reflect::set(&result, &"errors".into(), &errors);
+    Ok(result)
+}
+
+/// Converts a decoded [`TokenTransferEvent`] into a plain JS object so callers
+/// don't need to know about the protobuf/prost types.
+pub(crate) fn event_to_js(event: &TokenTransferEvent) -> JsValue {
+    let obj = Object::new();
+    let _ = Reflect::set(
+        &obj,
+        &"ledgerSequence".into(),
+        &JsValue::from(event.ledger_sequence),
+    );
+    let _ = Reflect::set(
+        &obj,
+        &"ledgerCloseTime".into(),
+        &JsValue::from(event.ledger_close_time as f64),
+    );
+    let _ = Reflect::set(&obj, &"txHash".into(), &JsValue::from_str(&event.tx_hash));
+    let _ = Reflect::set(
+        &obj,
+        &"fromAccount".into(),
+        &JsValue::from_str(&event.from_account),
+    );
+    let _ = Reflect::set(
+        &obj,
+        &"toAccount".into(),
+        &JsValue::from_str(&event.to_account),
+    );
+    let _ = Reflect::set(&obj, &"amount".into(), &JsValue::from_str(&event.amount));
+
+    if let Some(asset) = &event.asset {
+        let asset_obj = Object::new();
+        let _ = Reflect::set(
+            &asset_obj,
+            &"code".into(),
+            &JsValue::from_str(&asset.code),
+        );
+        let _ = Reflect::set(
+            &asset_obj,
+            &"issuer".into(),
+            &JsValue::from_str(&asset.issuer),
+        );
+        let _ = Reflect::set(&obj, &"asset".into(), &asset_obj);
+    }
+
+    obj.into()
+}
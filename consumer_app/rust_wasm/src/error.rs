@@ -0,0 +1,80 @@
+//! Error types surfaced to JS across the streaming API.
+
+use std::any::Any;
+
+use js_sys::{Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+/// Failure modes `EventClient`/`TtpEventStream` can report, distinct from an
+/// opaque string so JS callers can branch on `kind` instead of parsing
+/// messages — e.g. prompt for re-auth on `Unauthorized`, but just retry on
+/// `Transport`.
+pub(crate) enum ClientError {
+    /// The server rejected the request/stream as unauthenticated or
+    /// forbidden.
+    Unauthorized(tonic::Status),
+    /// The gRPC-web codec couldn't decode a single message (a malformed
+    /// `TokenTransferEvent` on the wire); the RPC connection itself is
+    /// still healthy. Tonic's codec surfaces a prost decode failure as
+    /// `Code::Internal` (occasionally `Code::DataLoss` for truncated
+    /// frames), not `Code::InvalidArgument` — that code means the *request*
+    /// was rejected wholesale and belongs under `Transport` instead.
+    Decode(String),
+    /// A panic while processing an otherwise-successfully-decoded event
+    /// (aggregation, filtering, JS conversion), caught so one bad event
+    /// can't take down the whole module.
+    Internal(String),
+    /// Any other transport or server-side failure.
+    Transport(tonic::Status),
+}
+
+impl ClientError {
+    pub(crate) fn from_status(status: tonic::Status) -> Self {
+        use tonic::Code;
+        match status.code() {
+            Code::Unauthenticated | Code::PermissionDenied => ClientError::Unauthorized(status),
+            Code::Internal | Code::DataLoss => {
+                ClientError::Decode(status.message().to_string())
+            }
+            _ => ClientError::Transport(status),
+        }
+    }
+
+    /// Converts a `catch_unwind` payload from processing a single event into
+    /// a recoverable error, so one panic doesn't abort the whole module.
+    pub(crate) fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic while processing event".to_string());
+        ClientError::Internal(message)
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            ClientError::Unauthorized(_) => "unauthorized",
+            ClientError::Decode(_) => "decode",
+            ClientError::Internal(_) => "internal",
+            ClientError::Transport(_) => "transport",
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            ClientError::Unauthorized(status) | ClientError::Transport(status) => {
+                status.message()
+            }
+            ClientError::Decode(message) | ClientError::Internal(message) => message,
+        }
+    }
+}
+
+impl From<ClientError> for JsValue {
+    fn from(err: ClientError) -> Self {
+        let obj = Object::new();
+        let _ = Reflect::set(&obj, &"kind".into(), &JsValue::from_str(err.kind()));
+        let _ = Reflect::set(&obj, &"message".into(), &JsValue::from_str(err.message()));
+        obj.into()
+    }
+}
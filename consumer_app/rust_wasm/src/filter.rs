@@ -0,0 +1,256 @@
+//! Client-side filtering and aggregation over decoded `TokenTransferEvent`s.
+//!
+//! `account_ids` narrows what the server sends; this module narrows (and
+//! summarizes) what the client surfaces, so a browser dashboard doesn't have
+//! to re-implement the same handful of predicates on every consumer.
+
+use std::collections::HashMap;
+
+use wasm_bindgen::prelude::*;
+
+use crate::proto::ingest::processors::token_transfer::TokenTransferEvent;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Any,
+    Incoming,
+    Outgoing,
+}
+
+/// A predicate matched against each event as it arrives: asset code/issuer,
+/// a minimum amount, and/or a transfer direction relative to one account.
+/// Registering several filters on a subscription OR's them together — an
+/// event is surfaced if it matches any one of them.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct TransferFilter {
+    asset_code: Option<String>,
+    asset_issuer: Option<String>,
+    min_amount: Option<i128>,
+    direction: Direction,
+    account: Option<String>,
+}
+
+#[wasm_bindgen]
+impl TransferFilter {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self {
+            asset_code: None,
+            asset_issuer: None,
+            min_amount: None,
+            direction: Direction::Any,
+            account: None,
+        }
+    }
+
+    #[wasm_bindgen(js_name = withAssetCode)]
+    pub fn with_asset_code(mut self, code: String) -> Self {
+        self.asset_code = Some(code);
+        self
+    }
+
+    #[wasm_bindgen(js_name = withAssetIssuer)]
+    pub fn with_asset_issuer(mut self, issuer: String) -> Self {
+        self.asset_issuer = Some(issuer);
+        self
+    }
+
+    /// `min_amount` is a decimal string, matching `TokenTransferEvent`'s
+    /// own stringified-i128 `amount` field. Unparsable input is ignored.
+    #[wasm_bindgen(js_name = withMinAmount)]
+    pub fn with_min_amount(mut self, min_amount: String) -> Self {
+        self.min_amount = min_amount.parse().ok();
+        self
+    }
+
+    /// `direction` is `"incoming"` or `"outgoing"`, relative to `account`;
+    /// any other value matches transfers in either direction.
+    #[wasm_bindgen(js_name = withDirection)]
+    pub fn with_direction(mut self, account: String, direction: String) -> Self {
+        self.direction = match direction.as_str() {
+            "incoming" => Direction::Incoming,
+            "outgoing" => Direction::Outgoing,
+            _ => Direction::Any,
+        };
+        self.account = Some(account);
+        self
+    }
+}
+
+impl Default for TransferFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransferFilter {
+    pub(crate) fn matches(&self, event: &TokenTransferEvent) -> bool {
+        if let Some(code) = &self.asset_code {
+            if event.asset.as_ref().is_none_or(|asset| &asset.code != code) {
+                return false;
+            }
+        }
+
+        if let Some(issuer) = &self.asset_issuer {
+            if event
+                .asset
+                .as_ref()
+                .is_none_or(|asset| &asset.issuer != issuer)
+            {
+                return false;
+            }
+        }
+
+        if let Some(min_amount) = self.min_amount {
+            let amount: i128 = event.amount.parse().unwrap_or(0);
+            if amount < min_amount {
+                return false;
+            }
+        }
+
+        if let Some(account) = &self.account {
+            let matches = match self.direction {
+                Direction::Incoming => &event.to_account == account,
+                Direction::Outgoing => &event.from_account == account,
+                Direction::Any => &event.from_account == account || &event.to_account == account,
+            };
+            if !matches {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// `(account, asset code, asset issuer)` — amounts for different assets
+/// aren't comparable, so balances are tracked per asset rather than summed
+/// across them. The native asset has no issuer, so it keys on `("", "")`.
+type BalanceKey = (String, String, String);
+
+fn balance_key(account: &str, asset_code: &str, asset_issuer: &str) -> BalanceKey {
+    (
+        account.to_string(),
+        asset_code.to_string(),
+        asset_issuer.to_string(),
+    )
+}
+
+/// Running per-account, per-asset net balance (credits minus debits) over a
+/// stream of `TokenTransferEvent`s, queryable at any point rather than only
+/// once the whole range has arrived.
+#[derive(Default)]
+pub(crate) struct BalanceAggregator {
+    net: HashMap<BalanceKey, i128>,
+}
+
+impl BalanceAggregator {
+    pub(crate) fn record(&mut self, event: &TokenTransferEvent) {
+        let amount: i128 = event.amount.parse().unwrap_or(0);
+        let (code, issuer) = event
+            .asset
+            .as_ref()
+            .map(|asset| (asset.code.as_str(), asset.issuer.as_str()))
+            .unwrap_or(("", ""));
+
+        *self
+            .net
+            .entry(balance_key(&event.from_account, code, issuer))
+            .or_insert(0) -= amount;
+        *self
+            .net
+            .entry(balance_key(&event.to_account, code, issuer))
+            .or_insert(0) += amount;
+    }
+
+    /// Net balance for `account` in the given asset, as a decimal string
+    /// (matching the stringified-i128 convention used elsewhere), or `"0"`
+    /// if unseen. Use `("", "")` for the native asset.
+    pub(crate) fn net_balance(&self, account: &str, asset_code: &str, asset_issuer: &str) -> String {
+        self.net
+            .get(&balance_key(account, asset_code, asset_issuer))
+            .copied()
+            .unwrap_or(0)
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::ingest::asset::Asset;
+
+    fn event(from: &str, to: &str, code: &str, issuer: &str, amount: &str) -> TokenTransferEvent {
+        TokenTransferEvent {
+            ledger_sequence: 1,
+            ledger_close_time: 0,
+            tx_hash: "tx".to_string(),
+            from_account: from.to_string(),
+            to_account: to.to_string(),
+            asset: if code.is_empty() {
+                None
+            } else {
+                Some(Asset {
+                    asset_type: 1,
+                    code: code.to_string(),
+                    issuer: issuer.to_string(),
+                })
+            },
+            amount: amount.to_string(),
+        }
+    }
+
+    #[test]
+    fn nets_assets_separately_per_account() {
+        let mut aggregator = BalanceAggregator::default();
+        // alice receives 100 USDC from bob, then sends 100 EUR to bob.
+        aggregator.record(&event("bob", "alice", "USDC", "issuer1", "100"));
+        aggregator.record(&event("alice", "bob", "EUR", "issuer2", "100"));
+
+        // An account receiving 100 USDC and sending 100 EUR must not net to
+        // "0" for either asset — they're not comparable quantities.
+        assert_eq!(aggregator.net_balance("alice", "USDC", "issuer1"), "100");
+        assert_eq!(aggregator.net_balance("alice", "EUR", "issuer2"), "-100");
+        assert_eq!(aggregator.net_balance("bob", "USDC", "issuer1"), "-100");
+        assert_eq!(aggregator.net_balance("bob", "EUR", "issuer2"), "100");
+    }
+
+    #[test]
+    fn native_asset_keys_on_empty_code_and_issuer() {
+        let mut aggregator = BalanceAggregator::default();
+        aggregator.record(&event("alice", "bob", "", "", "50"));
+
+        assert_eq!(aggregator.net_balance("alice", "", ""), "-50");
+        assert_eq!(aggregator.net_balance("bob", "", ""), "50");
+    }
+
+    #[test]
+    fn unseen_account_asset_nets_to_zero() {
+        let aggregator = BalanceAggregator::default();
+        assert_eq!(aggregator.net_balance("nobody", "USDC", "issuer1"), "0");
+    }
+
+    #[test]
+    fn direction_filter_matches_relative_to_account() {
+        let incoming = TransferFilter::new().with_direction("alice".to_string(), "incoming".to_string());
+        let outgoing = TransferFilter::new().with_direction("alice".to_string(), "outgoing".to_string());
+
+        let deposit = event("bob", "alice", "", "", "10");
+        let withdrawal = event("alice", "bob", "", "", "10");
+
+        assert!(incoming.matches(&deposit));
+        assert!(!incoming.matches(&withdrawal));
+        assert!(outgoing.matches(&withdrawal));
+        assert!(!outgoing.matches(&deposit));
+    }
+
+    #[test]
+    fn min_amount_filter_excludes_smaller_transfers() {
+        let filter = TransferFilter::new().with_min_amount("100".to_string());
+
+        assert!(filter.matches(&event("a", "b", "", "", "100")));
+        assert!(!filter.matches(&event("a", "b", "", "", "99")));
+    }
+}
+
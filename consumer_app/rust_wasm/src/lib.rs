@@ -1,5 +1,17 @@
 use wasm_bindgen::prelude::*;
 
+mod auth;
+mod client;
+mod error;
+mod filter;
+mod proto;
+mod reconnect;
+mod stream;
+
+pub use filter::TransferFilter;
+pub use reconnect::ReconnectConfig;
+pub use stream::TtpEventStream;
+
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
 #[cfg(feature = "wee_alloc")]
@@ -20,6 +32,7 @@ macro_rules! console_log {
 #[wasm_bindgen]
 pub struct EventClient {
     server_address: String,
+    auth_token: Option<String>,
 }
 
 #[wasm_bindgen]
@@ -28,7 +41,19 @@ impl EventClient {
     pub fn new(server_address: String) -> Self {
         console_error_panic_hook::set_once();
         console_log!("Creating EventClient with server address: {}", server_address);
-        Self { server_address }
+        Self {
+            server_address,
+            auth_token: None,
+        }
+    }
+
+    /// Attach a bearer token sent as `authorization: Bearer <token>` on every
+    /// subsequent `GetTTPEvents` request. Consumes and returns `self` so it
+    /// can be chained onto the constructor: `new EventClient(addr).withAuthToken(tok)`.
+    #[wasm_bindgen(js_name = withAuthToken)]
+    pub fn with_auth_token(mut self, token: String) -> Self {
+        self.auth_token = Some(token);
+        self
     }
 
     #[wasm_bindgen]
@@ -36,33 +61,59 @@ impl EventClient {
         format!("EventClient connected to: {}", self.server_address)
     }
     
-    /// Get TTP events with account filtering support
-    /// This is a placeholder implementation - the full gRPC client will be added
-    /// when dependency issues are resolved
+    /// Fetch TTP events for `[start_ledger, end_ledger]`, optionally filtered
+    /// to `account_ids`, and resolve once the whole range has streamed in, as
+    /// `{events, errors}` — two separate arrays so a malformed message never
+    /// gets mixed in among real events.
+    ///
+    /// This waits for the entire range before returning, which is fine for
+    /// small backfills but blocks the caller for large ones. For a UI that
+    /// wants to render events incrementally, use [`EventClient::subscribe`].
     #[wasm_bindgen]
-    pub async fn get_ttp_events(&self, start_ledger: u32, end_ledger: u32, account_ids: Vec<String>) -> Result<String, JsValue> {
+    pub async fn get_ttp_events(&self, start_ledger: u32, end_ledger: u32, account_ids: Vec<String>) -> Result<js_sys::Object, JsValue> {
         let filter_info = if account_ids.is_empty() {
             "all accounts".to_string()
         } else {
             format!("accounts: {}", account_ids.join(", "))
         };
-        
+
         console_log!("Requesting events from ledger {} to {} for {}", start_ledger, end_ledger, filter_info);
-        
-        // TODO: Implement actual gRPC client call with account filtering
-        // let request = GetEventsRequest {
-        //     start_ledger,
-        //     end_ledger,
-        //     account_ids,
-        // };
-        // let response = self.grpc_client.get_ttp_events(request).await?;
-        
-        Ok(format!("Mock response: would request events from {} to {} for {}", start_ledger, end_ledger, filter_info))
+
+        client::fetch_events(
+            &self.server_address,
+            self.auth_token.clone(),
+            start_ledger,
+            end_ledger,
+            account_ids,
+        )
+        .await
     }
-}
 
-// This is a temporary simplified version to test WASM compilation
-// The full implementation with gRPC client functionality will be added back
-// once we resolve the dependency issues
-//
-// NOTE: The protobuf modules have been commented out temporarily 
\ No newline at end of file
+    /// Subscribe to TTP events for `[start_ledger, end_ledger]` and drive the
+    /// stream incrementally via the returned [`TtpEventStream`] instead of
+    /// waiting for the whole range to arrive. `reconnect` controls retries
+    /// after a dropped connection (pass `undefined` to use the defaults);
+    /// `filters` narrows which events `recv()` surfaces (empty means every
+    /// event passes) while the stream's running balance aggregator still
+    /// tracks the whole range regardless.
+    #[wasm_bindgen]
+    pub fn subscribe(
+        &self,
+        start_ledger: u32,
+        end_ledger: u32,
+        account_ids: Vec<String>,
+        reconnect: Option<ReconnectConfig>,
+        filters: Vec<TransferFilter>,
+    ) -> TtpEventStream {
+        console_log!("Subscribing to events from ledger {} to {}", start_ledger, end_ledger);
+        TtpEventStream::spawn(
+            self.server_address.clone(),
+            self.auth_token.clone(),
+            start_ledger,
+            end_ledger,
+            account_ids,
+            reconnect.unwrap_or_default(),
+            filters,
+        )
+    }
+} 
\ No newline at end of file
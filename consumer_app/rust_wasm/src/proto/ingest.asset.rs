@@ -0,0 +1,32 @@
+// This file is @generated by prost-build.
+/// Mirrors the asset representation used across the ttp-processor-demo
+/// pipeline (ledger ingestion -> token transfer processor -> this client).
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Asset {
+    #[prost(enumeration = "asset::AssetType", tag = "1")]
+    pub asset_type: i32,
+    #[prost(string, tag = "2")]
+    pub code: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub issuer: ::prost::alloc::string::String,
+}
+/// Nested message and enum types in `Asset`.
+pub mod asset {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum AssetType {
+        Native = 0,
+        CreditAlphanum4 = 1,
+        CreditAlphanum12 = 2,
+    }
+    impl AssetType {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                AssetType::Native => "NATIVE",
+                AssetType::CreditAlphanum4 => "CREDIT_ALPHANUM4",
+                AssetType::CreditAlphanum12 => "CREDIT_ALPHANUM12",
+            }
+        }
+    }
+}
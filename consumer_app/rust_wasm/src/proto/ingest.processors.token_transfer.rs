@@ -0,0 +1,21 @@
+// This file is @generated by prost-build.
+/// A single token transfer observed while ingesting a ledger, emitted by the
+/// token_transfer processor and streamed to clients by the event service.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenTransferEvent {
+    #[prost(uint32, tag = "1")]
+    pub ledger_sequence: u32,
+    #[prost(uint64, tag = "2")]
+    pub ledger_close_time: u64,
+    #[prost(string, tag = "3")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub from_account: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub to_account: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "6")]
+    pub asset: ::core::option::Option<super::super::asset::Asset>,
+    /// Stringified i128 amount, in the asset's smallest unit.
+    #[prost(string, tag = "7")]
+    pub amount: ::prost::alloc::string::String,
+}
@@ -0,0 +1,29 @@
+//! Generated protobuf/gRPC bindings.
+//!
+//! `build.rs` points `tonic_build` at `src/proto` (instead of `OUT_DIR`) and
+//! the `*.rs` files here are checked in, because the wasm toolchains we ship
+//! this client from don't reliably have `protoc` on `PATH`. Regenerate them
+//! by running a normal `cargo build` on a machine that does, then commit the
+//! diff.
+
+pub mod ingest {
+    pub mod asset {
+        // The generated `Asset` message nests an `AssetType` enum in a
+        // same-named inner `mod asset`; that's prost's doing, not ours.
+        #[allow(clippy::module_inception)]
+        mod generated {
+            include!("ingest.asset.rs");
+        }
+        pub use generated::*;
+    }
+
+    pub mod processors {
+        pub mod token_transfer {
+            include!("ingest.processors.token_transfer.rs");
+        }
+    }
+}
+
+pub mod event_service {
+    include!("event_service.rs");
+}
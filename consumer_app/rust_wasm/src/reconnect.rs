@@ -0,0 +1,94 @@
+//! Reconnection policy for [`TtpEventStream`](crate::stream::TtpEventStream).
+//!
+//! gRPC-web streams run over `fetch`, which gets torn down far more often
+//! than a long-lived HTTP/2 connection would (backgrounded tabs, proxy
+//! idle timeouts, ...). `ReconnectConfig` bounds how many times in a row the
+//! stream driver re-opens `GetTTPEvents` after a transport error and how long
+//! it waits between attempts.
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+/// Retry/backoff knobs for automatic reconnection. The constructor takes all
+/// three values explicitly; for the defaults (5 retries, 250 ms initial
+/// backoff, capped at 8 s) pass `undefined` to `EventClient::subscribe`, or
+/// use [`ReconnectConfig::default`] from Rust.
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct ReconnectConfig {
+    pub(crate) max_retries: u32,
+    pub(crate) initial_backoff_ms: u32,
+    pub(crate) max_backoff_ms: u32,
+}
+
+#[wasm_bindgen]
+impl ReconnectConfig {
+    #[wasm_bindgen(constructor)]
+    pub fn new(max_retries: u32, initial_backoff_ms: u32, max_backoff_ms: u32) -> Self {
+        Self {
+            max_retries,
+            initial_backoff_ms,
+            max_backoff_ms,
+        }
+    }
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            initial_backoff_ms: 250,
+            max_backoff_ms: 8_000,
+        }
+    }
+}
+
+/// Exponential backoff (capped at `max_backoff_ms`) for the given 1-based
+/// retry attempt.
+pub(crate) fn backoff_ms(config: &ReconnectConfig, attempt: u32) -> u32 {
+    let shift = attempt.saturating_sub(1).min(16);
+    config
+        .initial_backoff_ms
+        .saturating_mul(1u32 << shift)
+        .min(config.max_backoff_ms)
+}
+
+/// Waits `ms` milliseconds via `window.setTimeout`, the only timer available
+/// to a wasm module running in a browser.
+pub(crate) async fn sleep_ms(ms: u32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        let window = web_sys::window().expect("no global `window` exists");
+        let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms as i32);
+    });
+    let _ = JsFuture::from(promise).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(initial: u32, max: u32) -> ReconnectConfig {
+        ReconnectConfig::new(u32::MAX, initial, max)
+    }
+
+    #[test]
+    fn doubles_each_attempt_until_capped() {
+        let config = config(250, 8_000);
+        assert_eq!(backoff_ms(&config, 1), 250);
+        assert_eq!(backoff_ms(&config, 2), 500);
+        assert_eq!(backoff_ms(&config, 3), 1_000);
+        assert_eq!(backoff_ms(&config, 4), 2_000);
+    }
+
+    #[test]
+    fn caps_at_max_backoff() {
+        let config = config(250, 1_000);
+        assert_eq!(backoff_ms(&config, 10), 1_000);
+    }
+
+    #[test]
+    fn never_overflows_on_large_attempts() {
+        let config = config(250, 8_000);
+        assert_eq!(backoff_ms(&config, u32::MAX), 8_000);
+    }
+}
@@ -0,0 +1,432 @@
+//! Pull-based streaming controller returned by [`EventClient::subscribe`](crate::EventClient::subscribe).
+//!
+//! `get_ttp_events` resolves once, after the whole `[start_ledger,
+//! end_ledger]` range has streamed in, which is unworkable for a UI that
+//! wants to render token transfers as they arrive over thousands of ledgers.
+//! `subscribe` instead returns a handle a JS caller drives incrementally:
+//! `poll()` checks readiness without consuming, `recv()` awaits the next
+//! event, and `stop()` tears the subscription down.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+use crate::client;
+use crate::error::ClientError;
+use crate::filter::{BalanceAggregator, TransferFilter};
+use crate::proto::ingest::processors::token_transfer::TokenTransferEvent;
+use crate::reconnect::{self, ReconnectConfig};
+
+/// Tracks which transfers have already been delivered, so a reconnect can
+/// resume from the boundary ledger (not `+1`, since a ledger can hold many
+/// transfers) without re-surfacing ones already sent.
+///
+/// `TokenTransferEvent` carries no per-transfer id — `tx_hash` identifies the
+/// *transaction*, and a single transaction routinely emits several transfers
+/// (path payments, multiple operations), so dedup can't key on identity.
+/// Instead this counts how many transfers were delivered from the boundary
+/// ledger and, on resume, skips exactly that many of the re-sent ones before
+/// accepting anything new from it.
+#[derive(Default)]
+struct DeliveryCursor {
+    last_ledger: Option<u32>,
+    delivered_in_last_ledger: u32,
+    /// Armed to `delivered_in_last_ledger` at the start of each connection
+    /// attempt; counts down as the server re-sends transfers already
+    /// delivered from the boundary ledger.
+    skip_remaining: u32,
+}
+
+impl DeliveryCursor {
+    /// The ledger a reconnect should re-request from: the boundary ledger
+    /// itself if we've delivered anything, otherwise the original start.
+    fn resume_ledger(&self, start_ledger: u32) -> u32 {
+        self.last_ledger.unwrap_or(start_ledger)
+    }
+
+    /// Arms the skip counter for a new connection attempt. Call once before
+    /// reading the first message of each attempt, including the first.
+    fn begin_connection(&mut self) {
+        self.skip_remaining = self.delivered_in_last_ledger;
+    }
+
+    /// Records `event` as delivered if it isn't one already re-sent from the
+    /// boundary ledger, returning whether it's new. Transfers in ledgers
+    /// before the boundary are stale (already fully delivered); transfers in
+    /// the boundary ledger are skipped up to `skip_remaining` and accepted
+    /// after that; anything past the boundary always advances it.
+    fn accept(&mut self, event: &TokenTransferEvent) -> bool {
+        match self.last_ledger {
+            Some(last) if event.ledger_sequence < last => false,
+            Some(last) if event.ledger_sequence == last => {
+                if self.skip_remaining > 0 {
+                    self.skip_remaining -= 1;
+                    false
+                } else {
+                    self.delivered_in_last_ledger += 1;
+                    true
+                }
+            }
+            _ => {
+                self.last_ledger = Some(event.ledger_sequence);
+                self.delivered_in_last_ledger = 1;
+                self.skip_remaining = 0;
+                true
+            }
+        }
+    }
+}
+
+/// One buffered item: either a decoded event (already converted to a plain
+/// JS object) or an error. A `Decode` or `Internal` error concerns only one
+/// event and the stream goes on after it; any other error is the last item.
+enum Item {
+    Event(JsValue),
+    Error(JsValue),
+}
+
+struct Shared {
+    buffered: VecDeque<Item>,
+    /// Set once the background task has nothing more to deliver, either
+    /// because the RPC finished, it errored, or `stop()` was called.
+    closed: bool,
+    /// Set by `stop()`; the background task checks this between messages and
+    /// tears the RPC down instead of reading further.
+    stop_requested: bool,
+    waker: Option<Waker>,
+    /// Predicates deciding which decoded events get buffered for `recv()`.
+    /// Empty means everything passes. Multiple filters are OR'd together.
+    filters: Vec<TransferFilter>,
+    /// Per-account net balance over every event seen, regardless of
+    /// `filters` — the aggregate reflects the whole range, not just what
+    /// `recv()` surfaced.
+    aggregator: BalanceAggregator,
+}
+
+impl Shared {
+    fn push(&mut self, item: Item) {
+        self.buffered.push_back(item);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn close(&mut self) {
+        self.closed = true;
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A live `GetTTPEvents` subscription that JS can poll incrementally.
+#[wasm_bindgen]
+pub struct TtpEventStream {
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl TtpEventStream {
+    pub(crate) fn spawn(
+        server_address: String,
+        auth_token: Option<String>,
+        start_ledger: u32,
+        end_ledger: u32,
+        account_ids: Vec<String>,
+        reconnect: ReconnectConfig,
+        filters: Vec<TransferFilter>,
+    ) -> Self {
+        let shared = Rc::new(RefCell::new(Shared {
+            buffered: VecDeque::new(),
+            closed: false,
+            stop_requested: false,
+            waker: None,
+            filters,
+            aggregator: BalanceAggregator::default(),
+        }));
+
+        spawn_local(drive(
+            shared.clone(),
+            server_address,
+            auth_token,
+            start_ledger,
+            end_ledger,
+            account_ids,
+            reconnect,
+        ));
+
+        Self { shared }
+    }
+}
+
+/// Opens the RPC and forwards events into `shared` until the whole
+/// `[start_ledger, end_ledger]` range is delivered or the caller calls
+/// `stop()`.
+///
+/// Only [`ClientError::Transport`] triggers a reconnect: it re-opens
+/// `GetTTPEvents` from the boundary ledger (tracked by `cursor`, which also
+/// skips the transfers from it that were already delivered), up to
+/// `reconnect.max_retries` consecutive times with backoff; the count resets
+/// whenever a message arrives. `Unauthorized` closes the
+/// subscription immediately —
+/// retrying a rejected auth token as if it were a network blip would just
+/// waste the retry budget. A `Decode` status on a single message is treated
+/// as a malformed event, not a dead stream: it's surfaced as a recoverable
+/// per-item error and the same stream keeps being read. A panic while
+/// aggregating/filtering/converting an otherwise-decoded event is caught the
+/// same way, surfaced as [`ClientError::Internal`].
+async fn drive(
+    shared: Rc<RefCell<Shared>>,
+    server_address: String,
+    auth_token: Option<String>,
+    start_ledger: u32,
+    end_ledger: u32,
+    account_ids: Vec<String>,
+    reconnect: ReconnectConfig,
+) {
+    let mut cursor = DeliveryCursor::default();
+    let mut attempt = 0u32;
+
+    'reconnect: loop {
+        let next_ledger = cursor.resume_ledger(start_ledger);
+        if shared.borrow().stop_requested || next_ledger > end_ledger {
+            break;
+        }
+        cursor.begin_connection();
+
+        let mut stream = match client::open_stream(
+            &server_address,
+            auth_token.clone(),
+            next_ledger,
+            end_ledger,
+            account_ids.clone(),
+        )
+        .await
+        {
+            Ok(stream) => stream,
+            Err(err @ ClientError::Transport(_)) if attempt < reconnect.max_retries => {
+                attempt += 1;
+                reconnect::sleep_ms(reconnect::backoff_ms(&reconnect, attempt)).await;
+                drop(err);
+                continue 'reconnect;
+            }
+            Err(err) => {
+                let mut shared = shared.borrow_mut();
+                shared.push(Item::Error(err.into()));
+                shared.close();
+                return;
+            }
+        };
+
+        loop {
+            if shared.borrow().stop_requested {
+                break 'reconnect;
+            }
+
+            match stream.message().await {
+                Ok(Some(event)) => {
+                    attempt = 0;
+                    if !cursor.accept(&event) {
+                        // Already delivered before the drop; the server
+                        // re-sent it from the resume ledger.
+                        continue;
+                    }
+
+                    // Guards aggregation/filtering/conversion, not the prost
+                    // decode itself (that already surfaced as a `Status`,
+                    // handled below as `ClientError::Decode`): the i128
+                    // balance arithmetic and JS conversion are the processing
+                    // steps this client controls that could still panic on a
+                    // single event without the whole stream being at fault.
+                    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                        let mut guard = shared.borrow_mut();
+                        guard.aggregator.record(&event);
+                        let passes = guard.filters.is_empty()
+                            || guard.filters.iter().any(|f| f.matches(&event));
+                        drop(guard);
+                        passes.then(|| client::event_to_js(&event))
+                    }));
+
+                    match outcome {
+                        Ok(Some(js_event)) => {
+                            shared.borrow_mut().push(Item::Event(js_event));
+                        }
+                        Ok(None) => {}
+                        Err(payload) => {
+                            shared
+                                .borrow_mut()
+                                .push(Item::Error(ClientError::from_panic(payload).into()));
+                        }
+                    }
+                }
+                Ok(None) => break 'reconnect,
+                Err(status) => match ClientError::from_status(status) {
+                    ClientError::Transport(status) => {
+                        if attempt >= reconnect.max_retries {
+                            shared
+                                .borrow_mut()
+                                .push(Item::Error(ClientError::Transport(status).into()));
+                            break 'reconnect;
+                        }
+                        attempt += 1;
+                        reconnect::sleep_ms(reconnect::backoff_ms(&reconnect, attempt)).await;
+                        continue 'reconnect;
+                    }
+                    ClientError::Decode(message) => {
+                        // Recoverable: this one message is lost, but the
+                        // stream keeps going rather than tearing down.
+                        shared
+                            .borrow_mut()
+                            .push(Item::Error(ClientError::Decode(message).into()));
+                        continue;
+                    }
+                    err => {
+                        shared.borrow_mut().push(Item::Error(err.into()));
+                        break 'reconnect;
+                    }
+                },
+            }
+        }
+    }
+
+    shared.borrow_mut().close();
+}
+
+/// Resolves to the next buffered item, or registers a waker if none is ready
+/// yet. Does not poll the underlying RPC itself; `drive` does that.
+struct Recv {
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl Future for Recv {
+    type Output = Result<JsValue, JsValue>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut shared = self.shared.borrow_mut();
+        match shared.buffered.pop_front() {
+            Some(Item::Event(event)) => Poll::Ready(Ok(event)),
+            Some(Item::Error(err)) => Poll::Ready(Err(err)),
+            None if shared.closed => Poll::Ready(Ok(JsValue::NULL)),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl TtpEventStream {
+    /// True if `recv()` would resolve immediately: an event, error, or
+    /// end-of-stream is already buffered.
+    #[wasm_bindgen]
+    pub fn poll(&self) -> bool {
+        let shared = self.shared.borrow();
+        !shared.buffered.is_empty() || shared.closed
+    }
+
+    /// Awaits the next event. Resolves to `null` once the stream has ended
+    /// (naturally or via `stop()`); rejects with `{kind, message}` on an
+    /// error. After a `decode` or `internal` rejection, later `recv()` calls
+    /// keep delivering events.
+    #[wasm_bindgen]
+    pub async fn recv(&self) -> Result<JsValue, JsValue> {
+        Recv {
+            shared: self.shared.clone(),
+        }
+        .await
+    }
+
+    /// Tears the subscription down. The in-flight RPC is abandoned after its
+    /// next message; any pending `recv()` resolves to `null`.
+    #[wasm_bindgen]
+    pub fn stop(&self) {
+        let mut shared = self.shared.borrow_mut();
+        shared.stop_requested = true;
+        shared.close();
+    }
+
+    /// Net balance (credits minus debits, as a decimal string) for `account`
+    /// in the given asset, across every event seen so far, independent of
+    /// any registered filters. Use `""`/`""` for the native asset. Safe to
+    /// call at any point, not just once the stream ends.
+    #[wasm_bindgen(js_name = netBalance)]
+    pub fn net_balance(&self, account: &str, asset_code: &str, asset_issuer: &str) -> String {
+        self.shared
+            .borrow()
+            .aggregator
+            .net_balance(account, asset_code, asset_issuer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(ledger_sequence: u32, tx_hash: &str) -> TokenTransferEvent {
+        TokenTransferEvent {
+            ledger_sequence,
+            ledger_close_time: 0,
+            tx_hash: tx_hash.to_string(),
+            from_account: "a".to_string(),
+            to_account: "b".to_string(),
+            asset: None,
+            amount: "1".to_string(),
+        }
+    }
+
+    #[test]
+    fn accepts_every_transfer_in_a_ledger_on_the_happy_path() {
+        // A single transaction routinely emits several transfers sharing one
+        // tx_hash; none of them should be dropped absent a reconnect.
+        let mut cursor = DeliveryCursor::default();
+        cursor.begin_connection();
+        assert!(cursor.accept(&event(100, "tx1")));
+        assert!(cursor.accept(&event(100, "tx1")));
+        assert!(cursor.accept(&event(100, "tx1")));
+        assert!(cursor.accept(&event(100, "tx2")));
+    }
+
+    #[test]
+    fn resume_skips_exactly_the_transfers_already_delivered() {
+        let mut cursor = DeliveryCursor::default();
+        cursor.begin_connection();
+        assert!(cursor.accept(&event(100, "tx1")));
+        assert!(cursor.accept(&event(100, "tx1")));
+        assert!(cursor.accept(&event(100, "tx1")));
+
+        // Connection drops mid-ledger; resume re-requests from the boundary
+        // ledger, which re-sends all of its transfers from the start.
+        assert_eq!(cursor.resume_ledger(50), 100);
+        cursor.begin_connection();
+        assert!(!cursor.accept(&event(100, "tx1")));
+        assert!(!cursor.accept(&event(100, "tx1")));
+        assert!(!cursor.accept(&event(100, "tx1")));
+        // A transfer beyond what was already delivered is accepted.
+        assert!(cursor.accept(&event(100, "tx2")));
+    }
+
+    #[test]
+    fn advances_past_a_fully_delivered_ledger() {
+        let mut cursor = DeliveryCursor::default();
+        cursor.begin_connection();
+        assert!(cursor.accept(&event(100, "tx1")));
+        assert!(cursor.accept(&event(101, "tx2")));
+
+        // Ledger 100 is now stale; a re-send of it (e.g. overlapping
+        // reconnect windows) must not resurrect it.
+        assert!(!cursor.accept(&event(100, "tx1")));
+    }
+
+    #[test]
+    fn resume_ledger_defaults_to_start_before_anything_is_delivered() {
+        let cursor = DeliveryCursor::default();
+        assert_eq!(cursor.resume_ledger(42), 42);
+    }
+}